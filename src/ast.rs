@@ -1,5 +1,15 @@
+use std::collections::BTreeMap;
 use time::{Date, Duration, OffsetDateTime, Time};
 
+/// An ISO 8601 duration, split into a calendar part that can't be expressed
+/// as a fixed span (`Y`/`M`, folded into whole months) and a day/time
+/// remainder (`W`/`D` and the `T` segment) that can.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalendarDuration {
+    pub months: i64,
+    pub duration: Duration,
+}
+
 /// primitiveLiteral
 #[derive(Debug, PartialEq, Clone)]
 pub enum Literal {
@@ -8,14 +18,18 @@ pub enum Literal {
     Date(Date),
     DateTimeOffset(OffsetDateTime),
     Time(Time),
-    /// decimal, double, single
+    /// double, single
     Float(f64),
+    /// decimal, kept at full precision
+    Decimal(rust_decimal::Decimal),
     GUID(String),
     /// sbyte, byte, int16, int32, int64
     Integer(i64),
     String(String),
-    Duration(Duration),
+    Duration(CalendarDuration),
     Binary(Vec<u8>),
+    Collection(Vec<Literal>),
+    Object(BTreeMap<String, Literal>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -24,8 +38,76 @@ pub enum Name {
     Qualified(Vec<String>),
 }
 
+/// A `$filter` binary operator, in the OData `boolCommonExpr`/`commonExpr`
+/// grammar's precedence order from loosest- to tightest-binding:
+/// `Or` < `And` < comparisons < `Add`/`Sub` < `Mul`/`Div`/`Mod`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BinaryOperator {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl BinaryOperator {
+    /// The OData `$filter` keyword for this operator, e.g. `Eq` -> `"eq"`.
+    /// Used by the serializer and the Python conversion layer; the parser's
+    /// operator table spells out its own keywords.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            BinaryOperator::Or => "or",
+            BinaryOperator::And => "and",
+            BinaryOperator::Eq => "eq",
+            BinaryOperator::Ne => "ne",
+            BinaryOperator::Gt => "gt",
+            BinaryOperator::Ge => "ge",
+            BinaryOperator::Lt => "lt",
+            BinaryOperator::Le => "le",
+            BinaryOperator::Add => "add",
+            BinaryOperator::Sub => "sub",
+            BinaryOperator::Mul => "mul",
+            BinaryOperator::Div => "div",
+            BinaryOperator::Mod => "mod",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UnaryOperator {
+    Not,
+    Negate,
+}
+
+impl UnaryOperator {
+    /// The OData `$filter` keyword/symbol for this operator, e.g. `Not` -> `"not"`.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            UnaryOperator::Not => "not",
+            UnaryOperator::Negate => "-",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum CommonExpr {
     Literal(Literal),
     Name(Name),
+    Binary {
+        op: BinaryOperator,
+        lhs: Box<CommonExpr>,
+        rhs: Box<CommonExpr>,
+    },
+    Unary {
+        op: UnaryOperator,
+        expr: Box<CommonExpr>,
+    },
 }
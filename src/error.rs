@@ -0,0 +1,156 @@
+//! A crate-level parse error that keeps the span (as the unconsumed input at
+//! the point of failure) and a typed reason, instead of nom's generic
+//! `ErrorKind`.
+use std::fmt;
+
+/// Why a literal failed to parse, independent of where in the input it
+/// occurred.
+#[derive(Debug, Clone)]
+pub enum OdataErrorReason {
+    InvalidGuid,
+    InvalidBase64(base64::DecodeError),
+    /// A date/time component (year, month, day, hour, ...) was out of range;
+    /// the inner error already names the offending component.
+    DateOutOfRange(time::error::ComponentRange),
+    UnterminatedString,
+    InvalidDuration,
+    InvalidDecimal,
+    InvalidFloat,
+    Syntax,
+}
+
+impl fmt::Display for OdataErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OdataErrorReason::InvalidGuid => write!(f, "invalid GUID"),
+            OdataErrorReason::InvalidBase64(e) => write!(f, "invalid base64: {e}"),
+            OdataErrorReason::DateOutOfRange(e) => write!(f, "{e}"),
+            OdataErrorReason::UnterminatedString => write!(f, "unterminated string literal"),
+            OdataErrorReason::InvalidDuration => write!(f, "invalid duration"),
+            OdataErrorReason::InvalidDecimal => write!(f, "invalid decimal"),
+            OdataErrorReason::InvalidFloat => write!(f, "invalid float"),
+            OdataErrorReason::Syntax => write!(f, "syntax error"),
+        }
+    }
+}
+
+/// A parse failure: the unconsumed input at the point it occurred (from
+/// which the byte offset into the original query can be recovered via
+/// [`OdataParseError::offset`]) plus a typed reason.
+#[derive(Debug, Clone)]
+pub struct OdataParseError<'a> {
+    pub input: &'a str,
+    pub reason: OdataErrorReason,
+}
+
+impl<'a> OdataParseError<'a> {
+    pub fn new(input: &'a str, reason: OdataErrorReason) -> Self {
+        Self { input, reason }
+    }
+
+    /// The byte offset into `full_input` at which this error occurred.
+    /// `full_input` must be the same string that was originally passed to
+    /// the top-level parser (or a slice of it that ends where it ends).
+    pub fn offset(&self, full_input: &str) -> usize {
+        full_input.len() - self.input.len()
+    }
+
+    /// Re-tags this error with a more specific reason, keeping its span.
+    fn with_reason(self, reason: OdataErrorReason) -> Self {
+        Self { reason, ..self }
+    }
+}
+
+impl<'a> fmt::Display for OdataParseError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (remaining input: {:?})", self.reason, self.input)
+    }
+}
+
+impl<'a> std::error::Error for OdataParseError<'a> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.reason {
+            OdataErrorReason::InvalidBase64(e) => Some(e),
+            OdataErrorReason::DateOutOfRange(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for OdataParseError<'a> {
+    fn from_error_kind(input: &'a str, _kind: nom::error::ErrorKind) -> Self {
+        Self::new(input, OdataErrorReason::Syntax)
+    }
+
+    fn append(_input: &'a str, _kind: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    // `alt` combines the errors of its branches with `or` rather than
+    // `append`. The default would keep whichever branch was tried last, which
+    // is usually the least informative one (e.g. `parse_literal`'s `object`
+    // branch failing immediately on input that was actually an almost-valid
+    // `duration'...'`). Keeping whichever side consumed more input instead
+    // surfaces the error from the branch that got furthest before failing.
+    fn or(self, other: Self) -> Self {
+        if other.input.len() <= self.input.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<'a> nom::error::FromExternalError<&'a str, base64::DecodeError> for OdataParseError<'a> {
+    fn from_external_error(
+        input: &'a str,
+        _kind: nom::error::ErrorKind,
+        e: base64::DecodeError,
+    ) -> Self {
+        Self::new(input, OdataErrorReason::InvalidBase64(e))
+    }
+}
+
+impl<'a> nom::error::FromExternalError<&'a str, time::error::ComponentRange>
+    for OdataParseError<'a>
+{
+    fn from_external_error(
+        input: &'a str,
+        _kind: nom::error::ErrorKind,
+        e: time::error::ComponentRange,
+    ) -> Self {
+        Self::new(input, OdataErrorReason::DateOutOfRange(e))
+    }
+}
+
+/// Shorthand for a nom parse result using [`OdataParseError`].
+pub type PResult<'a, O> = nom::IResult<&'a str, O, OdataParseError<'a>>;
+
+/// Wraps a parser so that any failure it produces is re-tagged with
+/// `reason`, while keeping the span at which it occurred.
+pub fn labeled<'a, O>(
+    reason: OdataErrorReason,
+    mut parser: impl FnMut(&'a str) -> PResult<'a, O>,
+) -> impl FnMut(&'a str) -> PResult<'a, O> {
+    move |inp| parser(inp).map_err(|e| e.map(|err| err.with_reason(reason.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ParseError;
+
+    #[test]
+    fn or_keeps_the_furthest_progress_error() {
+        let full_input = "duration'P1Y1W'";
+        let shallow = OdataParseError::new(full_input, OdataErrorReason::Syntax);
+        let deep = OdataParseError::new(&full_input[14..], OdataErrorReason::InvalidDuration);
+
+        let combined = shallow.clone().or(deep.clone());
+        assert_eq!(combined.offset(full_input), deep.offset(full_input));
+
+        // Order shouldn't matter: the deeper error always wins.
+        let combined = deep.or(shallow);
+        assert_eq!(combined.offset(full_input), 14);
+    }
+}
@@ -1,21 +1,53 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-mod ast;
-mod parser;
+pub mod ast;
+pub mod error;
+pub mod parser;
+mod pyconv;
+pub mod serialize;
 #[cfg(test)]
 mod test_util;
 
-/// Formats the sum of two numbers as string.
+use ast::CommonExpr;
+
+fn parse_expr_or_pyerr(odata_query: &str) -> PyResult<CommonExpr> {
+    match parser::expr::parse(odata_query) {
+        Ok(("", expr)) => Ok(expr),
+        Ok((rest, _)) => Err(PyValueError::new_err(format!(
+            "unexpected trailing input {rest:?} at offset {}",
+            odata_query.len() - rest.len()
+        ))),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(PyValueError::new_err(format!(
+            "{} at offset {}",
+            e.reason,
+            e.offset(odata_query)
+        ))),
+        Err(nom::Err::Incomplete(_)) => Err(PyValueError::new_err("incomplete input")),
+    }
+}
+
+/// Parses an OData `$filter`-style expression and returns the resulting
+/// value as native Python data.
+#[pyfunction]
+fn parse_odata(py: Python<'_>, odata_query: &str) -> PyResult<PyObject> {
+    let expr = parse_expr_or_pyerr(odata_query)?;
+    pyconv::common_expr_to_py(py, &expr)
+}
+
+/// Parses an OData `$filter`-style expression and re-serializes it as
+/// canonical OData text, so that e.g. equivalent queries can be compared or
+/// cached by their normalized form.
 #[pyfunction]
-fn parse_odata(odata_query: &str) -> PyResult<bool> {
-    let ast = parser::expr::parse(odata_query);
-    println!("{:?}", ast);
-    Ok(true)
+fn normalize_odata(odata_query: &str) -> PyResult<String> {
+    let expr = parse_expr_or_pyerr(odata_query)?;
+    Ok(expr.to_query_string())
 }
 
 /// A Python module implemented in Rust.
 #[pymodule]
 fn _odata_query(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_odata, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_odata, m)?)?;
     Ok(())
 }
@@ -1,13 +1,270 @@
-use crate::ast::CommonExpr;
+use crate::ast::{BinaryOperator, CommonExpr, UnaryOperator};
+use crate::error::PResult;
 use crate::parser::literal::parse_literal;
 use crate::parser::name::parse_name;
 use nom::branch::alt;
-use nom::combinator::map;
-use nom::IResult;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{char, multispace0, multispace1};
+use nom::combinator::{map, value};
+use nom::sequence::{delimited, pair, preceded, terminated};
 
-pub fn parse(odata_query: &str) -> IResult<&str, CommonExpr> {
+/// The left binding power a `$filter` operator parses at, lowest first.
+/// Comparisons are non-chaining in the OData grammar but we treat them as
+/// left-associative here, same as the arithmetic tiers.
+fn binary_operator(inp: &str) -> PResult<'_, (BinaryOperator, u8)> {
+    alt((
+        value((BinaryOperator::Or, 1), tag_no_case("or")),
+        value((BinaryOperator::And, 2), tag_no_case("and")),
+        value((BinaryOperator::Eq, 3), tag_no_case("eq")),
+        value((BinaryOperator::Ne, 3), tag_no_case("ne")),
+        value((BinaryOperator::Ge, 3), tag_no_case("ge")),
+        value((BinaryOperator::Gt, 3), tag_no_case("gt")),
+        value((BinaryOperator::Le, 3), tag_no_case("le")),
+        value((BinaryOperator::Lt, 3), tag_no_case("lt")),
+        value((BinaryOperator::Add, 4), tag_no_case("add")),
+        value((BinaryOperator::Sub, 4), tag_no_case("sub")),
+        value((BinaryOperator::Mul, 5), tag_no_case("mul")),
+        value((BinaryOperator::Div, 5), tag_no_case("div")),
+        value((BinaryOperator::Mod, 5), tag_no_case("mod")),
+    ))(inp)
+}
+
+/// An operator token, requiring trailing whitespace so e.g. `ge` doesn't
+/// match the start of an identifier like `general`.
+fn parse_operator(inp: &str) -> PResult<'_, (BinaryOperator, u8)> {
+    terminated(binary_operator, multispace1)(inp)
+}
+
+fn parse_primary(inp: &str) -> PResult<'_, CommonExpr> {
+    let parenthesized = delimited(
+        pair(char('('), multispace0),
+        parse_expr(0),
+        pair(multispace0, char(')')),
+    );
     let literal = map(parse_literal, CommonExpr::Literal);
     let name = map(parse_name, CommonExpr::Name);
 
-    alt((literal, name))(odata_query)
+    alt((parenthesized, literal, name))(inp)
+}
+
+fn parse_unary(inp: &str) -> PResult<'_, CommonExpr> {
+    let not = map(
+        preceded(pair(tag_no_case("not"), multispace1), parse_expr(3)),
+        |expr| CommonExpr::Unary {
+            op: UnaryOperator::Not,
+            expr: Box::new(expr),
+        },
+    );
+    let negate = map(preceded(char('-'), parse_unary), |expr| CommonExpr::Unary {
+        op: UnaryOperator::Negate,
+        expr: Box::new(expr),
+    });
+
+    // `not` must be tried before `parse_primary`: its `name` branch has no
+    // reserved-word check, so on input like `not Discontinued eq true` it
+    // would otherwise happily parse the bare token `not` as an identifier.
+    // `negate` must come *after* `parse_primary`, though: literal integers,
+    // decimals, and floats already parse their own leading sign, so `-10`
+    // should stay a single `Literal::Integer(-10)` rather than becoming
+    // `Unary{Negate, Literal::Integer(10)}`. `negate` only kicks in once
+    // `parse_primary` has failed outright, e.g. for `-Price` or `-(...)`.
+    alt((not, parse_primary, negate))(inp)
+}
+
+/// Precedence climbing: parse a primary/unary operand, then repeatedly
+/// consume binary operators whose binding power is at least `min_bp`,
+/// recursing with `bp + 1` to gather the right-hand side so that operators
+/// of the same precedence fold left-associatively.
+fn parse_expr(min_bp: u8) -> impl FnMut(&str) -> PResult<'_, CommonExpr> {
+    move |inp| {
+        let (mut rest, mut lhs) = parse_unary(inp)?;
+
+        while let Ok((after_op, (op, bp))) = preceded(multispace0, parse_operator)(rest) {
+            if bp < min_bp {
+                break;
+            }
+            let (after_rhs, rhs) = parse_expr(bp + 1)(after_op)?;
+            lhs = CommonExpr::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+            rest = after_rhs;
+        }
+
+        Ok((rest, lhs))
+    }
+}
+
+pub fn parse(odata_query: &str) -> PResult<'_, CommonExpr> {
+    parse_expr(0)(odata_query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Literal, Name};
+    use crate::test_util::assert_parsed_to;
+
+    fn lit(l: Literal) -> CommonExpr {
+        CommonExpr::Literal(l)
+    }
+
+    fn name(n: &str) -> CommonExpr {
+        CommonExpr::Name(Name::Identifier(n.to_string()))
+    }
+
+    fn binary(op: BinaryOperator, lhs: CommonExpr, rhs: CommonExpr) -> CommonExpr {
+        CommonExpr::Binary {
+            op,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    #[test]
+    fn parse_bare_literal() {
+        assert_parsed_to(parse("42"), lit(Literal::Integer(42)));
+    }
+
+    #[test]
+    fn parse_bare_name() {
+        assert_parsed_to(parse("Price"), name("Price"));
+    }
+
+    #[test]
+    fn parse_simple_comparison() {
+        assert_parsed_to(
+            parse("Price gt 10"),
+            binary(BinaryOperator::Gt, name("Price"), lit(Literal::Integer(10))),
+        );
+    }
+
+    #[test]
+    fn parse_precedence_and_binds_tighter_than_or() {
+        // `A or B and C` should be `A or (B and C)`, not `(A or B) and C`.
+        assert_parsed_to(
+            parse("true or false and true"),
+            binary(
+                BinaryOperator::Or,
+                lit(Literal::Boolean(true)),
+                binary(
+                    BinaryOperator::And,
+                    lit(Literal::Boolean(false)),
+                    lit(Literal::Boolean(true)),
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_left_associative_same_precedence() {
+        // `1 sub 2 sub 3` should be `(1 sub 2) sub 3`.
+        assert_parsed_to(
+            parse("1 sub 2 sub 3"),
+            binary(
+                BinaryOperator::Sub,
+                binary(
+                    BinaryOperator::Sub,
+                    lit(Literal::Integer(1)),
+                    lit(Literal::Integer(2)),
+                ),
+                lit(Literal::Integer(3)),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_arithmetic_binds_tighter_than_comparison() {
+        assert_parsed_to(
+            parse("Price add 1 gt 10"),
+            binary(
+                BinaryOperator::Gt,
+                binary(BinaryOperator::Add, name("Price"), lit(Literal::Integer(1))),
+                lit(Literal::Integer(10)),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_parenthesized_overrides_precedence() {
+        assert_parsed_to(
+            parse("(Price gt 10) and (Discontinued eq false)"),
+            binary(
+                BinaryOperator::And,
+                binary(BinaryOperator::Gt, name("Price"), lit(Literal::Integer(10))),
+                binary(
+                    BinaryOperator::Eq,
+                    name("Discontinued"),
+                    lit(Literal::Boolean(false)),
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_unary_not() {
+        assert_parsed_to(
+            parse("not Discontinued eq true"),
+            CommonExpr::Unary {
+                op: UnaryOperator::Not,
+                expr: Box::new(binary(
+                    BinaryOperator::Eq,
+                    name("Discontinued"),
+                    lit(Literal::Boolean(true)),
+                )),
+            },
+        );
+    }
+
+    #[test]
+    fn parse_unary_negate() {
+        assert_parsed_to(
+            parse("-Price gt -10"),
+            binary(
+                BinaryOperator::Gt,
+                CommonExpr::Unary {
+                    op: UnaryOperator::Negate,
+                    expr: Box::new(name("Price")),
+                },
+                lit(Literal::Integer(-10)),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_full_example() {
+        assert_parsed_to(
+            parse("Price gt 10 and (Name eq 'foo' or Discontinued eq false)"),
+            binary(
+                BinaryOperator::And,
+                binary(BinaryOperator::Gt, name("Price"), lit(Literal::Integer(10))),
+                binary(
+                    BinaryOperator::Or,
+                    binary(
+                        BinaryOperator::Eq,
+                        name("Name"),
+                        lit(Literal::String("foo".to_string())),
+                    ),
+                    binary(
+                        BinaryOperator::Eq,
+                        name("Discontinued"),
+                        lit(Literal::Boolean(false)),
+                    ),
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn string_that_looks_like_a_duration_body_stays_a_string() {
+        assert_parsed_to(
+            parse("Name eq 'P1Y'"),
+            binary(
+                BinaryOperator::Eq,
+                name("Name"),
+                lit(Literal::String("P1Y".to_string())),
+            ),
+        );
+    }
 }
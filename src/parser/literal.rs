@@ -1,17 +1,18 @@
-use crate::ast::Literal;
+use crate::ast::{CalendarDuration, Literal};
+use crate::error::{labeled, OdataErrorReason, OdataParseError, PResult};
 use base64::{alphabet, engine, Engine as _};
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag, tag_no_case, take_while, take_while_m_n};
-use nom::character::complete::{char, digit1, one_of};
+use nom::character::complete::{char, digit1, multispace0, one_of};
 use nom::combinator::{cut, map, map_res, opt, recognize, value, verify};
-use nom::error::{Error, ParseError};
-use nom::multi::many0;
-use nom::sequence::{delimited, pair, preceded, terminated, tuple};
-use nom::IResult;
+use nom::multi::{many0, separated_list0};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
 use nom::ParseTo;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 use time::{Date, Duration, Month, OffsetDateTime, Time, UtcOffset};
 
-pub fn parse_float(inp: &str) -> IResult<&str, f64> {
+pub fn parse_float(inp: &str) -> PResult<'_, f64> {
     let (i, float_str) = recognize(verify(
         tuple((
             opt(one_of("+-")),
@@ -25,14 +26,77 @@ pub fn parse_float(inp: &str) -> IResult<&str, f64> {
 
     match float_str.parse_to() {
         Some(f) => Ok((i, f)),
-        None => Err(nom::Err::Error(Error::from_error_kind(
+        None => Err(nom::Err::Error(OdataParseError::new(
             i,
-            nom::error::ErrorKind::Float,
+            OdataErrorReason::InvalidFloat,
         ))),
     }
 }
 
-pub fn parse_string(inp: &str) -> IResult<&str, String> {
+// OData's `decimal` form: an optional sign, an integer part, and a mandatory
+// fraction, with no exponent. This is distinct from `Float`, which is the
+// only form that can carry an exponent or the `INF`/`NaN` special values.
+pub fn parse_decimal(inp: &str) -> PResult<'_, Decimal> {
+    let (i, dec_str) = recognize(tuple((opt(one_of("+-")), digit1, char('.'), digit1)))(inp)?;
+
+    // An exponent means this is actually a `Float`; let that branch handle it.
+    if i.starts_with(['e', 'E']) {
+        return Err(nom::Err::Error(OdataParseError::new(
+            inp,
+            OdataErrorReason::InvalidFloat,
+        )));
+    }
+
+    match dec_str.parse::<Decimal>() {
+        Ok(d) => Ok((i, d)),
+        Err(_) => Err(nom::Err::Error(OdataParseError::new(
+            i,
+            OdataErrorReason::InvalidDecimal,
+        ))),
+    }
+}
+
+// A numeric span followed by one of the Edm type suffixes (`L` for
+// Edm.Int64, `M`/`m` for Edm.Decimal, `d`/`f` for Edm.Double/Edm.Single).
+// The span is kept as text and parsed directly into the target type rather
+// than routed through `f64`, so e.g. `79228162514264337593543950335M` keeps
+// its full precision as a `Decimal` instead of rounding through a float.
+pub fn parse_suffixed_number(inp: &str) -> PResult<'_, Literal> {
+    let (rest, num_str) = recognize(tuple((
+        opt(one_of("+-")),
+        digit1,
+        opt(pair(char('.'), digit1)),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), cut(digit1)))),
+    )))(inp)?;
+    let (rest, suffix) = one_of("LMmdf")(rest)?;
+
+    match suffix {
+        'L' => match num_str.parse::<i64>() {
+            Ok(i) => Ok((rest, Literal::Integer(i))),
+            Err(_) => Err(nom::Err::Error(OdataParseError::new(
+                inp,
+                OdataErrorReason::InvalidFloat,
+            ))),
+        },
+        'M' | 'm' => match num_str.parse::<Decimal>() {
+            Ok(d) => Ok((rest, Literal::Decimal(d))),
+            Err(_) => Err(nom::Err::Error(OdataParseError::new(
+                inp,
+                OdataErrorReason::InvalidDecimal,
+            ))),
+        },
+        // 'd' / 'f'
+        _ => match num_str.parse::<f64>() {
+            Ok(f) => Ok((rest, Literal::Float(f))),
+            Err(_) => Err(nom::Err::Error(OdataParseError::new(
+                inp,
+                OdataErrorReason::InvalidFloat,
+            ))),
+        },
+    }
+}
+
+pub fn parse_string(inp: &str) -> PResult<'_, String> {
     let part = alt((
         is_not("'"),
         // Double SQUOTE within a string escapes to a single SQUOTE
@@ -40,24 +104,26 @@ pub fn parse_string(inp: &str) -> IResult<&str, String> {
     ));
 
     let str_parts = delimited(char('\''), many0(part), char('\''));
-    map(str_parts, |p| p.join(""))(inp)
+    let parser = map(str_parts, |p: Vec<&str>| p.join(""));
+
+    labeled(OdataErrorReason::UnterminatedString, parser)(inp)
 }
 
 // nom has its own `is_hex_digit`, but it only works on `u8`
 fn is_hex_digit(c: char) -> bool {
-    c.is_digit(16)
+    c.is_ascii_hexdigit()
 }
 
 fn is_digit(c: char) -> bool {
-    c.is_digit(10)
+    c.is_ascii_digit()
 }
 
 fn is_base64url_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '='
 }
 
-pub fn parse_guid(inp: &str) -> IResult<&str, String> {
-    let (i, guid_str) = recognize(tuple((
+pub fn parse_guid(inp: &str) -> PResult<'_, String> {
+    let parser = recognize(tuple((
         take_while_m_n(8, 8, is_hex_digit),
         char('-'),
         take_while_m_n(4, 4, is_hex_digit),
@@ -67,19 +133,21 @@ pub fn parse_guid(inp: &str) -> IResult<&str, String> {
         take_while_m_n(4, 4, is_hex_digit),
         char('-'),
         take_while_m_n(12, 12, is_hex_digit),
-    )))(inp)?;
+    )));
+
+    let (i, guid_str) = labeled(OdataErrorReason::InvalidGuid, parser)(inp)?;
 
     Ok((i, guid_str.to_string()))
 }
 
-pub fn parse_year(inp: &str) -> IResult<&str, i32> {
+pub fn parse_year(inp: &str) -> PResult<'_, i32> {
     let parser = recognize(tuple((opt(char('-')), take_while_m_n(4, 4, is_digit))));
 
     // Infallible, as 4 digits always fit an i32
     map(parser, |s: &str| s.parse::<i32>().unwrap())(inp)
 }
 
-pub fn n_digits_between(inp: &str, n_digits: usize, min: u8, max: u8) -> IResult<&str, u8> {
+pub fn n_digits_between(inp: &str, n_digits: usize, min: u8, max: u8) -> PResult<'_, u8> {
     let digits = take_while_m_n(n_digits, n_digits, is_digit);
 
     verify(
@@ -88,18 +156,15 @@ pub fn n_digits_between(inp: &str, n_digits: usize, min: u8, max: u8) -> IResult
     )(inp)
 }
 
-pub fn parse_month(inp: &str) -> IResult<&str, Month> {
-    map_res(
-        |i| n_digits_between(i, 2, 1, 12),
-        |val| Month::try_from(val),
-    )(inp)
+pub fn parse_month(inp: &str) -> PResult<'_, Month> {
+    map_res(|i| n_digits_between(i, 2, 1, 12), Month::try_from)(inp)
 }
 
-pub fn parse_day(inp: &str) -> IResult<&str, u8> {
+pub fn parse_day(inp: &str) -> PResult<'_, u8> {
     n_digits_between(inp, 2, 1, 31)
 }
 
-pub fn parse_date(inp: &str) -> IResult<&str, Date> {
+pub fn parse_date(inp: &str) -> PResult<'_, Date> {
     // OData `year`s can be negative, conflicting with ISO8601.
     // So we don't use `time::*::parse`
     let parser = tuple((parse_year, char('-'), parse_month, char('-'), parse_day));
@@ -107,15 +172,15 @@ pub fn parse_date(inp: &str) -> IResult<&str, Date> {
     map_res(parser, |(y, _, m, _, d)| Date::from_calendar_date(y, m, d))(inp)
 }
 
-pub fn parse_hour(inp: &str) -> IResult<&str, u8> {
+pub fn parse_hour(inp: &str) -> PResult<'_, u8> {
     n_digits_between(inp, 2, 0, 24)
 }
 
-pub fn parse_minute(inp: &str) -> IResult<&str, u8> {
+pub fn parse_minute(inp: &str) -> PResult<'_, u8> {
     n_digits_between(inp, 2, 0, 59)
 }
 
-pub fn parse_fractional_seconds(inp: &str) -> IResult<&str, u32> {
+pub fn parse_fractional_seconds(inp: &str) -> PResult<'_, u32> {
     // Parses the "fractionalSeconds" after the dot to an amount expressed in
     // nanoseconds
     let digits = take_while_m_n(1, 12, is_digit);
@@ -127,7 +192,7 @@ pub fn parse_fractional_seconds(inp: &str) -> IResult<&str, u32> {
     map(nanos, |s: String| s.parse::<u32>().unwrap())(inp)
 }
 
-pub fn parse_second(inp: &str) -> IResult<&str, (u8, u32)> {
+pub fn parse_second(inp: &str) -> PResult<'_, (u8, u32)> {
     let parser = tuple((
         |i| n_digits_between(i, 2, 0, 59),
         opt(preceded(char('.'), parse_fractional_seconds)),
@@ -136,7 +201,7 @@ pub fn parse_second(inp: &str) -> IResult<&str, (u8, u32)> {
     map(parser, |(sec, frac)| (sec, frac.unwrap_or(0)))(inp)
 }
 
-pub fn parse_time(inp: &str) -> IResult<&str, Time> {
+pub fn parse_time(inp: &str) -> PResult<'_, Time> {
     let parser = tuple((
         parse_hour,
         char(':'),
@@ -150,7 +215,7 @@ pub fn parse_time(inp: &str) -> IResult<&str, Time> {
     })(inp)
 }
 
-pub fn parse_tzoffset(inp: &str) -> IResult<&str, UtcOffset> {
+pub fn parse_tzoffset(inp: &str) -> PResult<'_, UtcOffset> {
     alt((
         value(UtcOffset::UTC, tag_no_case("Z")),
         map(
@@ -168,10 +233,14 @@ pub fn parse_tzoffset(inp: &str) -> IResult<&str, UtcOffset> {
     ))(inp)
 }
 
-pub fn parse_datetime(inp: &str) -> IResult<&str, OffsetDateTime> {
+pub fn parse_datetime(inp: &str) -> PResult<'_, OffsetDateTime> {
+    // The OData ABNF only allows `T` between the date and time parts, but we
+    // also accept a plain space so that datetimes copied out of e.g. SQL or
+    // log output parse without modification. `T` remains the only form the
+    // serializer emits, so round-tripping a parsed value is unaffected.
     let parser = tuple((
         parse_date,
-        tag_no_case("T"),
+        alt((tag_no_case("T"), tag(" "))),
         parse_time,
         opt(parse_tzoffset),
     ));
@@ -181,62 +250,94 @@ pub fn parse_datetime(inp: &str) -> IResult<&str, OffsetDateTime> {
     })(inp)
 }
 
-pub fn parse_duration(inp: &str) -> IResult<&str, Duration> {
+pub fn parse_duration(inp: &str) -> PResult<'_, CalendarDuration> {
+    let years = map(terminated(digit1, tag_no_case("Y")), |s: &str| {
+        s.parse::<i64>().unwrap()
+    });
+    let months = map(terminated(digit1, tag_no_case("M")), |s: &str| {
+        s.parse::<i64>().unwrap()
+    });
+    let weeks = map(terminated(digit1, tag_no_case("W")), |s: &str| {
+        Duration::weeks(s.parse::<i64>().unwrap())
+    });
     let days = map(terminated(digit1, tag_no_case("D")), |s: &str| {
         Duration::days(s.parse::<i64>().unwrap())
     });
+
+    // `W` is mutually exclusive with the other date-part designators, so it
+    // gets its own branch rather than slotting in alongside `Y`/`M`/`D`.
+    let date_part = alt((
+        map(weeks, |w| (0i64, w, true)),
+        map(tuple((opt(years), opt(months), opt(days))), |(y, m, d)| {
+            (
+                y.unwrap_or(0) * 12 + m.unwrap_or(0),
+                d.unwrap_or(Duration::ZERO),
+                y.is_some() || m.is_some() || d.is_some(),
+            )
+        }),
+    ));
+
     let hours = map(terminated(digit1, tag_no_case("H")), |s: &str| {
         Duration::hours(s.parse::<i64>().unwrap())
     });
     let mins = map(terminated(digit1, tag_no_case("M")), |s: &str| {
         Duration::minutes(s.parse::<i64>().unwrap())
     });
-
-    let _s = recognize(tuple((digit1, opt(preceded(char('.'), digit1)))));
-    let secs = map(terminated(_s, tag_no_case("S")), |s: &str| {
+    let secs_digits = recognize(tuple((digit1, opt(preceded(char('.'), digit1)))));
+    let secs = map(terminated(secs_digits, tag_no_case("S")), |s: &str| {
         Duration::seconds_f64(s.parse::<f64>().unwrap())
     });
 
-    let time_duration = map(
-        tuple((tag_no_case("T"), opt(hours), opt(mins), opt(secs))),
-        |(_, h, m, s)| {
-            let hours = h.unwrap_or(Duration::ZERO);
-            let minutes = m.unwrap_or(Duration::ZERO);
-            let seconds = s.unwrap_or(Duration::ZERO);
-            hours.saturating_add(minutes).saturating_add(seconds)
+    let time_part = map(
+        opt(preceded(
+            tag_no_case("T"),
+            verify(
+                tuple((opt(hours), opt(mins), opt(secs))),
+                |(h, m, s)| h.is_some() || m.is_some() || s.is_some(),
+            ),
+        )),
+        |t| match t {
+            Some((h, m, s)) => (
+                h.unwrap_or(Duration::ZERO)
+                    .saturating_add(m.unwrap_or(Duration::ZERO))
+                    .saturating_add(s.unwrap_or(Duration::ZERO)),
+                true,
+            ),
+            None => (Duration::ZERO, false),
         },
     );
 
     let duration_val = map(
-        tuple((
-            opt(one_of("+-")),
-            tag_no_case("P"),
-            opt(days),
-            opt(time_duration),
-        )),
-        |(sign, _, d, t)| {
-            let days = d.unwrap_or(Duration::ZERO);
-            let time = t.unwrap_or(Duration::ZERO);
-            let res = days.saturating_add(time);
+        // At least one designator must be present: a bare `P` is invalid.
+        verify(
+            tuple((opt(one_of("+-")), tag_no_case("P"), date_part, time_part)),
+            |(_, _, (_, _, has_date), (_, has_time))| *has_date || *has_time,
+        ),
+        |(sign, _, (months, date_time, _), (time, _))| {
+            let duration = date_time.saturating_add(time);
             match sign {
-                Some('-') => -1 * res,
-                _ => res,
+                Some('-') => CalendarDuration {
+                    months: -months,
+                    duration: -duration,
+                },
+                _ => CalendarDuration { months, duration },
             }
         },
     );
 
-    delimited(
-        tuple((opt(tag_no_case("duration")), char('\''))),
-        duration_val,
-        char('\''),
-    )(inp)
+    // The `duration` prefix is mandatory: a bare-quoted form would make any
+    // plain string whose contents happen to look like a duration body (e.g.
+    // `'P1Y'`) silently parse as `Literal::Duration` instead of
+    // `Literal::String`, with no way to disambiguate after the fact.
+    let parser = delimited(tuple((tag_no_case("duration"), char('\''))), duration_val, char('\''));
+
+    labeled(OdataErrorReason::InvalidDuration, parser)(inp)
 }
 
-pub fn parse_binary(inp: &str) -> IResult<&str, Vec<u8>> {
+pub fn parse_binary(inp: &str) -> PResult<'_, Vec<u8>> {
     let binval = take_while(is_base64url_char);
     let parser = delimited(tag_no_case("binary'"), binval, char('\''));
 
-    // TODO: map base64::DecodeError onto a nom Error for clarity
     map_res(parser, |b64| {
         // We make no assumptions about how the client handles b64 padding:
         let cfg = engine::GeneralPurposeConfig::new()
@@ -246,7 +347,52 @@ pub fn parse_binary(inp: &str) -> IResult<&str, Vec<u8>> {
     })(inp)
 }
 
-pub fn parse_literal(inp: &str) -> IResult<&str, Literal> {
+/// A bracket-delimited, comma-separated list of literals, e.g.
+/// `['Food','Beverages']`. The `$filter` expression parser has no `in`
+/// operator, so a collection is only ever a plain operand.
+pub fn parse_collection(inp: &str) -> PResult<'_, Vec<Literal>> {
+    let item = preceded(multispace0, parse_literal);
+    let mut parser = delimited(
+        pair(char('['), multispace0),
+        separated_list0(char(','), item),
+        pair(multispace0, char(']')),
+    );
+
+    parser(inp)
+}
+
+// Complex-object literals use plain JSON-style double-quoted keys, not the
+// single-quoted, ''-escaped strings OData uses elsewhere.
+fn parse_object_key(inp: &str) -> PResult<'_, String> {
+    map(
+        delimited(char('"'), take_while(|c| c != '"'), char('"')),
+        |s: &str| s.to_string(),
+    )(inp)
+}
+
+/// A brace-delimited `"key": value` map, e.g. `{"City":'Redmond',"Zip":98052}`.
+pub fn parse_object(inp: &str) -> PResult<'_, BTreeMap<String, Literal>> {
+    let entry = separated_pair(
+        preceded(multispace0, parse_object_key),
+        tuple((multispace0, char(':'), multispace0)),
+        parse_literal,
+    );
+    let parser = delimited(
+        pair(char('{'), multispace0),
+        separated_list0(char(','), entry),
+        pair(multispace0, char('}')),
+    );
+
+    map(parser, |entries| entries.into_iter().collect())(inp)
+}
+
+/// Parses any OData `primitiveLiteral`: `null`/`true`/`false`, the temporal
+/// forms (`Date`, `DateTimeOffset`, `TimeOfDay`, `duration'...'`), `Guid`,
+/// numbers (with or without an Edm type suffix), strings, `binary'...'`, and
+/// collection/object literals. The temporal and `Guid` forms are tried ahead
+/// of the numeric branches since they also start with digits - e.g. a bare
+/// date would otherwise be swallowed by the integer branch as its year.
+pub fn parse_literal(inp: &str) -> PResult<'_, Literal> {
     let null = value(Literal::Null, tag("null"));
 
     let bool = alt((
@@ -265,14 +411,32 @@ pub fn parse_literal(inp: &str) -> IResult<&str, Literal> {
     let string = map(parse_string, Literal::String);
     let guid = map(parse_guid, Literal::GUID);
     let binary = map(parse_binary, Literal::Binary);
+    let decimal = map(parse_decimal, Literal::Decimal);
 
     let date = map(parse_date, Literal::Date);
     let time = map(parse_time, Literal::Time);
     let datetime = map(parse_datetime, Literal::DateTimeOffset);
     let duration = map(parse_duration, Literal::Duration);
 
+    let collection = map(parse_collection, Literal::Collection);
+    let object = map(parse_object, Literal::Object);
+
     alt((
-        null, duration, bool, string, datetime, date, time, guid, float, int, binary,
+        null,
+        duration,
+        bool,
+        string,
+        datetime,
+        date,
+        time,
+        guid,
+        parse_suffixed_number,
+        decimal,
+        float,
+        int,
+        binary,
+        collection,
+        object,
     ))(inp)
 }
 
@@ -305,8 +469,8 @@ mod tests {
 
     #[test]
     fn parse_float() {
-        assert_parsed_to(parse_literal("0.1"), Literal::Float(0.1));
-        assert_parsed_to(parse_literal("-0.1"), Literal::Float(-0.1));
+        // A bare fraction with no exponent is a `Decimal`, not a `Float`;
+        // see `parse_decimal` below.
         assert_parsed_to(parse_literal("1e10"), Literal::Float(1e10));
         assert_parsed_to(parse_literal("-1e10"), Literal::Float(-1e10));
         assert_parsed_to(parse_literal("1e-10"), Literal::Float(1e-10));
@@ -318,7 +482,7 @@ mod tests {
         // NaN never tests equal:
         match parse_literal("NaN") {
             Ok(("", Literal::Float(nan))) => assert!(nan.is_nan()),
-            _ => assert!(false),
+            _ => unreachable!(),
         };
     }
 
@@ -335,10 +499,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_decimal() {
+        assert_parsed_to(
+            parse_literal("0.1"),
+            Literal::Decimal(Decimal::new(1, 1)),
+        );
+        assert_parsed_to(
+            parse_literal("-0.1"),
+            Literal::Decimal(Decimal::new(-1, 1)),
+        );
+        // A value with more significant digits than an f64 mantissa can hold
+        // without rounding:
+        assert_parsed_to(
+            parse_literal("12345678901234567.89"),
+            Literal::Decimal("12345678901234567.89".parse().unwrap()),
+        );
+
+        // Exponents and special values still belong to `Float`:
+        assert_parsed_to(parse_literal("1.5e10"), Literal::Float(1.5e10));
+    }
+
+    #[test]
+    fn parse_suffixed_numeric_literal() {
+        assert_parsed_to(parse_literal("123L"), Literal::Integer(123));
+        assert_parsed_to(parse_literal("1.5M"), Literal::Decimal(Decimal::new(15, 1)));
+        assert_parsed_to(parse_literal("1.5m"), Literal::Decimal(Decimal::new(15, 1)));
+        // The `M` suffix keeps full precision, unlike a bare float literal.
+        assert_parsed_to(
+            parse_literal("79228162514264337593543950335M"),
+            Literal::Decimal("79228162514264337593543950335".parse().unwrap()),
+        );
+        assert_parsed_to(parse_literal("2.0f"), Literal::Float(2.0));
+        assert_parsed_to(parse_literal("2.0d"), Literal::Float(2.0));
+    }
+
+    #[test]
+    fn temporal_and_guid_literals_take_priority_over_numbers() {
+        // These all start with digits that could otherwise be swallowed by
+        // the integer/float branches, so the ordering of `alt` in
+        // `parse_literal` matters.
+        assert_parsed_to(
+            parse_literal("2023-01-01"),
+            Literal::Date(Date::from_calendar_date(2023, Month::January, 1).unwrap()),
+        );
+        assert_parsed_to(
+            parse_literal("2023-01-01T00:00:00Z"),
+            Literal::DateTimeOffset(
+                Date::from_calendar_date(2023, Month::January, 1)
+                    .unwrap()
+                    .with_time(Time::from_hms(0, 0, 0).unwrap())
+                    .assume_offset(UtcOffset::UTC),
+            ),
+        );
+        assert_parsed_to(
+            parse_literal("12345678-1234-1234-1234-123456789abc"),
+            Literal::GUID("12345678-1234-1234-1234-123456789abc".to_string()),
+        );
+    }
+
     #[test]
     fn parse_guid() {
         let guid = "d13efbec-aa20-47f4-8756-c38852488b6e";
-        assert_parsed_to(parse_literal(&guid), Literal::GUID(guid.to_string()));
+        assert_parsed_to(parse_literal(guid), Literal::GUID(guid.to_string()));
         assert_parsed_to(
             parse_literal(&guid.to_ascii_uppercase()),
             Literal::GUID(guid.to_ascii_uppercase()),
@@ -421,31 +644,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_datetime_space_separated() {
+        assert_parsed_to(
+            parse_literal("2023-01-01 00:00"),
+            Literal::DateTimeOffset(
+                Date::from_calendar_date(2023, Month::January, 1)
+                    .unwrap()
+                    .with_time(Time::from_hms(0, 0, 0).unwrap())
+                    .assume_offset(UtcOffset::UTC),
+            ),
+        );
+        assert_parsed_to(
+            parse_literal("2023-01-01 00:00:01.1"),
+            Literal::DateTimeOffset(
+                Date::from_calendar_date(2023, Month::January, 1)
+                    .unwrap()
+                    .with_time(Time::from_hms_milli(0, 0, 1, 100).unwrap())
+                    .assume_offset(UtcOffset::UTC),
+            ),
+        );
+        assert_parsed_to(
+            parse_literal("2023-01-01 00:00+02:00"),
+            Literal::DateTimeOffset(
+                Date::from_calendar_date(2023, Month::January, 1)
+                    .unwrap()
+                    .with_time(Time::from_hms(0, 0, 0).unwrap())
+                    .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap()),
+            ),
+        );
+    }
+
+    fn calendar_duration(months: i64, duration: Duration) -> Literal {
+        Literal::Duration(CalendarDuration { months, duration })
+    }
+
     #[test]
     fn parse_duration() {
         assert_parsed_to(
             parse_literal("duration'P1D'"),
-            Literal::Duration(Duration::days(1)),
+            calendar_duration(0, Duration::days(1)),
         );
         assert_parsed_to(
             parse_literal("duration'PT1H'"),
-            Literal::Duration(Duration::hours(1)),
+            calendar_duration(0, Duration::hours(1)),
         );
         assert_parsed_to(
             parse_literal("duration'PT1M'"),
-            Literal::Duration(Duration::minutes(1)),
+            calendar_duration(0, Duration::minutes(1)),
         );
         assert_parsed_to(
             parse_literal("duration'PT1S'"),
-            Literal::Duration(Duration::seconds(1)),
+            calendar_duration(0, Duration::seconds(1)),
         );
         assert_parsed_to(
             parse_literal("duration'PT1.2S'"),
-            Literal::Duration(Duration::seconds_f64(1.2)),
+            calendar_duration(0, Duration::seconds_f64(1.2)),
         );
         assert_parsed_to(
             parse_literal("duration'P1DT2H3M4.5S'"),
-            Literal::Duration(
+            calendar_duration(
+                0,
                 Duration::days(1)
                     + Duration::hours(2)
                     + Duration::minutes(3)
@@ -454,13 +713,40 @@ mod tests {
         );
         assert_parsed_to(
             parse_literal("duration'-P1D'"),
-            Literal::Duration(Duration::days(-1)),
+            calendar_duration(0, Duration::days(-1)),
+        );
+    }
+
+    #[test]
+    fn bare_quoted_duration_body_is_a_string_not_a_duration() {
+        // The `duration` prefix is mandatory: without it, a quoted duration
+        // body is indistinguishable from an ordinary string and must parse
+        // as one.
+        assert_parsed_to(parse_literal("'P1D'"), Literal::String("P1D".to_string()));
+        assert_parsed_to(parse_literal("'P1Y'"), Literal::String("P1Y".to_string()));
+    }
+
+    #[test]
+    fn parse_duration_calendar_designators() {
+        assert_parsed_to(parse_literal("duration'P1Y'"), calendar_duration(12, Duration::ZERO));
+        assert_parsed_to(parse_literal("duration'P2M'"), calendar_duration(2, Duration::ZERO));
+        assert_parsed_to(
+            parse_literal("duration'P3W'"),
+            calendar_duration(0, Duration::weeks(3)),
+        );
+        assert_parsed_to(
+            parse_literal("duration'P1Y2M3D'"),
+            calendar_duration(14, Duration::days(3)),
         );
-        assert_parsed_to(parse_literal("'P1D'"), Literal::Duration(Duration::days(1)));
         assert_parsed_to(
-            parse_literal("'-P1D'"),
-            Literal::Duration(Duration::days(-1)),
+            parse_literal("duration'-P1Y1M'"),
+            calendar_duration(-13, Duration::ZERO),
         );
+
+        // `W` can't mix with the other date-part designators:
+        assert!(parse_literal("duration'P1Y1W'").is_err());
+        // A bare `P` has no components, and is invalid:
+        assert!(parse_literal("duration'P'").is_err());
     }
 
     #[test]
@@ -479,4 +765,64 @@ mod tests {
             Literal::Binary(data.to_vec()),
         );
     }
+
+    #[test]
+    fn typed_error_reasons() {
+        // Qualified with `super::` because this module also has zero-arg
+        // tests named `parse_binary`/`parse_guid`/`parse_date`, which would
+        // otherwise shadow the real parser functions of the same name.
+        assert!(matches!(
+            super::parse_binary("binary'a'"),
+            Err(nom::Err::Error(OdataParseError {
+                reason: OdataErrorReason::InvalidBase64(_),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            super::parse_guid("zzzzzzzz-zzzz-zzzz-zzzz-zzzzzzzzzzzz"),
+            Err(nom::Err::Error(OdataParseError {
+                reason: OdataErrorReason::InvalidGuid,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            super::parse_date("2023-02-30"),
+            Err(nom::Err::Error(OdataParseError {
+                reason: OdataErrorReason::DateOutOfRange(_),
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn parse_collection() {
+        assert_parsed_to(parse_literal("[]"), Literal::Collection(vec![]));
+        assert_parsed_to(
+            parse_literal("[1,2,3]"),
+            Literal::Collection(vec![
+                Literal::Integer(1),
+                Literal::Integer(2),
+                Literal::Integer(3),
+            ]),
+        );
+        assert_parsed_to(
+            parse_literal("['Food', 'Beverages']"),
+            Literal::Collection(vec![
+                Literal::String("Food".to_string()),
+                Literal::String("Beverages".to_string()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn parse_object() {
+        assert_parsed_to(parse_literal("{}"), Literal::Object(BTreeMap::new()));
+        assert_parsed_to(
+            parse_literal(r#"{"City":'Redmond',"Zip":98052}"#),
+            Literal::Object(BTreeMap::from([
+                ("City".to_string(), Literal::String("Redmond".to_string())),
+                ("Zip".to_string(), Literal::Integer(98052)),
+            ])),
+        );
+    }
 }
@@ -1,11 +1,11 @@
 use crate::ast::Name;
+use crate::error::PResult;
 use nom::branch::alt;
 use nom::bytes::complete::{take_while, take_while_m_n};
 use nom::character::complete::char;
 use nom::combinator::{map, recognize};
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
-use nom::IResult;
 
 fn _is_odata_id_leading(inp: char) -> bool {
     inp.is_alphabetic() || inp == '_'
@@ -15,7 +15,7 @@ fn _is_odata_id(inp: char) -> bool {
     inp.is_alphanumeric() || inp == '_'
 }
 
-pub fn parse_identifier(inp: &str) -> IResult<&str, String> {
+pub fn parse_identifier(inp: &str) -> PResult<'_, String> {
     let parser = recognize(tuple((
         take_while_m_n(1, 1, _is_odata_id_leading),
         take_while(_is_odata_id),
@@ -24,11 +24,11 @@ pub fn parse_identifier(inp: &str) -> IResult<&str, String> {
     map(parser, |s: &str| s.to_string())(inp)
 }
 
-pub fn parse_optionally_qualified(inp: &str) -> IResult<&str, Vec<String>> {
+pub fn parse_optionally_qualified(inp: &str) -> PResult<'_, Vec<String>> {
     separated_list1(char('.'), parse_identifier)(inp)
 }
 
-pub fn parse_name(inp: &str) -> IResult<&str, Name> {
+pub fn parse_name(inp: &str) -> PResult<'_, Name> {
     let identifier = map(
         parse_optionally_qualified,
         |parts: Vec<String>| match parts.len() {
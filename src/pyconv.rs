@@ -0,0 +1,109 @@
+//! Converts the parser's AST into native Python objects for the `_odata_query`
+//! extension module.
+use crate::ast::{CommonExpr, Literal};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+fn offset_to_tzinfo(datetime_mod: &PyModule, offset: time::UtcOffset) -> PyResult<&PyAny> {
+    let timezone = datetime_mod.getattr("timezone")?;
+    if offset == time::UtcOffset::UTC {
+        return timezone.getattr("utc");
+    }
+
+    let delta = datetime_mod
+        .getattr("timedelta")?
+        .call1((0, offset.whole_seconds()))?;
+    timezone.call1((delta,))
+}
+
+fn literal_to_py(py: Python<'_>, literal: &Literal) -> PyResult<PyObject> {
+    match literal {
+        Literal::Null => Ok(py.None()),
+        Literal::Boolean(b) => Ok(b.into_py(py)),
+        Literal::Integer(i) => Ok(i.into_py(py)),
+        Literal::Float(v) => Ok(v.into_py(py)),
+        Literal::String(s) => Ok(s.into_py(py)),
+        Literal::Binary(bytes) => Ok(PyBytes::new(py, bytes).into_py(py)),
+        Literal::GUID(g) => Ok(py.import("uuid")?.getattr("UUID")?.call1((g,))?.into_py(py)),
+        Literal::Decimal(d) => Ok(py
+            .import("decimal")?
+            .getattr("Decimal")?
+            .call1((d.to_string(),))?
+            .into_py(py)),
+        Literal::Date(d) => Ok(py
+            .import("datetime")?
+            .getattr("date")?
+            .call1((d.year(), d.month() as u8, d.day()))?
+            .into_py(py)),
+        Literal::Time(t) => Ok(py
+            .import("datetime")?
+            .getattr("time")?
+            .call1((t.hour(), t.minute(), t.second(), t.microsecond()))?
+            .into_py(py)),
+        Literal::DateTimeOffset(dt) => {
+            let dt_mod = py.import("datetime")?;
+            let tzinfo = offset_to_tzinfo(dt_mod, dt.offset())?;
+            Ok(dt_mod
+                .getattr("datetime")?
+                .call1((
+                    dt.year(),
+                    dt.month() as u8,
+                    dt.day(),
+                    dt.hour(),
+                    dt.minute(),
+                    dt.second(),
+                    dt.microsecond(),
+                    tzinfo,
+                ))?
+                .into_py(py))
+        }
+        Literal::Duration(cd) => {
+            // `timedelta` has no notion of calendar months, so each month is
+            // approximated as 30 days - the same trade-off the rest of this
+            // conversion makes by mapping Edm.Duration onto a type Python
+            // consumers already know.
+            let whole_days = cd.duration.whole_days();
+            let days = cd.months * 30 + whole_days;
+            let rest_seconds = (cd.duration - time::Duration::days(whole_days)).as_seconds_f64();
+            Ok(py
+                .import("datetime")?
+                .getattr("timedelta")?
+                .call1((days, rest_seconds))?
+                .into_py(py))
+        }
+        Literal::Collection(items) => {
+            let values = items
+                .iter()
+                .map(|item| literal_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(values.into_py(py))
+        }
+        Literal::Object(entries) => {
+            let dict = PyDict::new(py);
+            for (key, value) in entries {
+                dict.set_item(key, literal_to_py(py, value)?)?;
+            }
+            Ok(dict.into_py(py))
+        }
+    }
+}
+
+pub fn common_expr_to_py(py: Python<'_>, expr: &CommonExpr) -> PyResult<PyObject> {
+    match expr {
+        CommonExpr::Literal(l) => literal_to_py(py, l),
+        CommonExpr::Name(n) => Ok(n.to_string().into_py(py)),
+        CommonExpr::Binary { op, lhs, rhs } => {
+            let dict = PyDict::new(py);
+            dict.set_item("op", op.keyword())?;
+            dict.set_item("lhs", common_expr_to_py(py, lhs)?)?;
+            dict.set_item("rhs", common_expr_to_py(py, rhs)?)?;
+            Ok(dict.into_py(py))
+        }
+        CommonExpr::Unary { op, expr } => {
+            let dict = PyDict::new(py);
+            dict.set_item("op", op.keyword())?;
+            dict.set_item("expr", common_expr_to_py(py, expr)?)?;
+            Ok(dict.into_py(py))
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Renders the parsed AST back into a canonical OData query string, so that
+//! `parse` followed by `to_string` round-trips.
+use crate::ast::{CalendarDuration, CommonExpr, Literal, Name, UnaryOperator};
+use base64::{alphabet, engine, Engine as _};
+use std::fmt;
+use time::{Date, Duration, Time, UtcOffset};
+
+fn format_date(d: Date) -> String {
+    let year = d.year();
+    let sign = if year < 0 { "-" } else { "" };
+    format!("{sign}{:04}-{:02}-{:02}", year.unsigned_abs(), d.month() as u8, d.day())
+}
+
+fn format_time(t: Time) -> String {
+    let nanos = t.nanosecond();
+    if nanos == 0 {
+        format!("{:02}:{:02}:{:02}", t.hour(), t.minute(), t.second())
+    } else {
+        let frac = format!("{nanos:09}");
+        let frac = frac.trim_end_matches('0');
+        format!(
+            "{:02}:{:02}:{:02}.{frac}",
+            t.hour(),
+            t.minute(),
+            t.second()
+        )
+    }
+}
+
+fn format_tzoffset(o: UtcOffset) -> String {
+    if o == UtcOffset::UTC {
+        return "Z".to_string();
+    }
+
+    let (h, m, _s) = o.as_hms();
+    let sign = if h < 0 { '-' } else { '+' };
+    format!("{sign}{:02}:{:02}", h.abs(), m.abs())
+}
+
+fn format_duration(cd: &CalendarDuration) -> String {
+    let negative = cd.months < 0 || cd.duration.is_negative();
+    let months = cd.months.unsigned_abs();
+    let duration = if negative { -cd.duration } else { cd.duration };
+
+    let mut body = String::from("P");
+    let years = months / 12;
+    let rem_months = months % 12;
+    if years > 0 {
+        body.push_str(&format!("{years}Y"));
+    }
+    if rem_months > 0 {
+        body.push_str(&format!("{rem_months}M"));
+    }
+
+    let days = duration.whole_days();
+    let rem = duration - Duration::days(days);
+    let hours = rem.whole_hours();
+    let rem = rem - Duration::hours(hours);
+    let minutes = rem.whole_minutes();
+    let seconds = (rem - Duration::minutes(minutes)).as_seconds_f64();
+
+    if days > 0 {
+        body.push_str(&format!("{days}D"));
+    }
+    if hours != 0 || minutes != 0 || seconds != 0.0 {
+        body.push('T');
+        if hours != 0 {
+            body.push_str(&format!("{hours}H"));
+        }
+        if minutes != 0 {
+            body.push_str(&format!("{minutes}M"));
+        }
+        if seconds != 0.0 {
+            body.push_str(&format!("{seconds}S"));
+        }
+    }
+    // A zero-valued duration has no designator beyond `P`; fall back to the
+    // smallest valid body rather than emitting a bare, invalid `P`.
+    if body == "P" {
+        body.push_str("T0S");
+    }
+
+    format!("duration'{}{body}'", if negative { "-" } else { "" })
+}
+
+fn format_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Null => write!(f, "null"),
+            Literal::Boolean(b) => write!(f, "{b}"),
+            Literal::Date(d) => write!(f, "{}", format_date(*d)),
+            Literal::DateTimeOffset(dt) => write!(
+                f,
+                "{}T{}{}",
+                format_date(dt.date()),
+                format_time(dt.time()),
+                format_tzoffset(dt.offset())
+            ),
+            Literal::Time(t) => write!(f, "{}", format_time(*t)),
+            Literal::Float(v) if v.is_nan() => write!(f, "NaN"),
+            Literal::Float(v) if v.is_infinite() && *v > 0.0 => write!(f, "INF"),
+            Literal::Float(v) if v.is_infinite() => write!(f, "-INF"),
+            Literal::Float(v) => write!(f, "{v:e}"),
+            Literal::Decimal(d) => {
+                // A whole-number `Decimal` (scale 0) displays with no `.`,
+                // which would reparse as `Literal::Integer` instead. Force a
+                // fractional part so the round trip stays a `Decimal`.
+                let s = d.to_string();
+                if s.contains('.') {
+                    write!(f, "{s}")
+                } else {
+                    write!(f, "{s}.0")
+                }
+            }
+            Literal::GUID(g) => write!(f, "{g}"),
+            Literal::Integer(i) => write!(f, "{i}"),
+            Literal::String(s) => write!(f, "{}", format_string(s)),
+            Literal::Duration(cd) => write!(f, "{}", format_duration(cd)),
+            Literal::Binary(b) => {
+                let cfg = engine::GeneralPurposeConfig::new();
+                let engine = engine::GeneralPurpose::new(&alphabet::URL_SAFE, cfg);
+                write!(f, "binary'{}'", engine.encode(b))
+            }
+            Literal::Collection(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Literal::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "\"{key}\":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Name::Identifier(s) => write!(f, "{s}"),
+            Name::Qualified(parts) => write!(f, "{}", parts.join(".")),
+        }
+    }
+}
+
+// Operands that are themselves binary/unary expressions are always
+// parenthesized. This loses round-trip minimality (an already-unambiguous
+// `a add b mul c` comes back out as `a add (b mul c)`) but guarantees the
+// reparsed AST matches, which is what callers actually need.
+fn format_operand(expr: &CommonExpr) -> String {
+    match expr {
+        CommonExpr::Binary { .. } | CommonExpr::Unary { .. } => format!("({expr})"),
+        CommonExpr::Literal(_) | CommonExpr::Name(_) => expr.to_string(),
+    }
+}
+
+impl fmt::Display for CommonExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommonExpr::Literal(l) => write!(f, "{l}"),
+            CommonExpr::Name(n) => write!(f, "{n}"),
+            CommonExpr::Binary { op, lhs, rhs } => write!(
+                f,
+                "{} {} {}",
+                format_operand(lhs),
+                op.keyword(),
+                format_operand(rhs)
+            ),
+            CommonExpr::Unary {
+                op: UnaryOperator::Not,
+                expr,
+            } => write!(f, "not {}", format_operand(expr)),
+            CommonExpr::Unary {
+                op: UnaryOperator::Negate,
+                expr,
+            } => write!(f, "-{}", format_operand(expr)),
+        }
+    }
+}
+
+impl CommonExpr {
+    /// Re-emits this expression as canonical OData `$filter` text, such that
+    /// parsing the result back yields an equal `CommonExpr`.
+    pub fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::expr;
+    use crate::parser::literal::parse_literal;
+
+    fn assert_round_trips(input: &str) {
+        let (_, parsed) = parse_literal(input).expect("input should parse");
+        let serialized = parsed.to_string();
+        let (rest, reparsed) =
+            parse_literal(&serialized).unwrap_or_else(|e| panic!("{serialized:?} didn't parse: {e}"));
+        assert!(rest.is_empty(), "Unparsed input: {rest}");
+        assert_eq!(reparsed, parsed, "serialized as {serialized:?}");
+    }
+
+    #[test]
+    fn round_trip_null_and_boolean() {
+        assert_round_trips("null");
+        assert_round_trips("true");
+        assert_round_trips("false");
+    }
+
+    #[test]
+    fn round_trip_integer() {
+        assert_round_trips("123456789");
+        assert_round_trips("-123456789");
+    }
+
+    #[test]
+    fn round_trip_float() {
+        assert_round_trips("1e10");
+        assert_round_trips("-1e10");
+        assert_round_trips("123.456e10");
+        assert_round_trips("INF");
+        assert_round_trips("-INF");
+    }
+
+    #[test]
+    fn round_trip_decimal() {
+        assert_round_trips("0.1");
+        assert_round_trips("-0.1");
+        assert_round_trips("12345678901234567.89");
+        // A whole-number Decimal (e.g. from an `M`-suffixed integer literal)
+        // must not serialize as a bare integer, or it reparses as
+        // `Literal::Integer` instead of `Literal::Decimal`.
+        assert_round_trips("123M");
+    }
+
+    #[test]
+    fn round_trip_string() {
+        assert_round_trips("'hello world'");
+        assert_round_trips("''");
+        assert_round_trips("'g''day sir'");
+    }
+
+    #[test]
+    fn round_trip_guid() {
+        assert_round_trips("d13efbec-aa20-47f4-8756-c38852488b6e");
+    }
+
+    #[test]
+    fn round_trip_date() {
+        assert_round_trips("2023-01-01");
+        assert_round_trips("-0001-01-01");
+    }
+
+    #[test]
+    fn round_trip_time() {
+        assert_round_trips("01:02");
+        assert_round_trips("01:02:03");
+        assert_round_trips("01:02:03.1");
+        assert_round_trips("01:02:03.000000001");
+    }
+
+    #[test]
+    fn round_trip_datetime() {
+        assert_round_trips("2023-01-01T00:00");
+        assert_round_trips("2023-01-01T00:00:01.1");
+        assert_round_trips("2023-01-01T00:00Z");
+        assert_round_trips("2023-01-01T00:00+02:00");
+    }
+
+    #[test]
+    fn round_trip_duration() {
+        assert_round_trips("duration'P1D'");
+        assert_round_trips("duration'PT1H'");
+        assert_round_trips("duration'PT1M'");
+        assert_round_trips("duration'PT1S'");
+        assert_round_trips("duration'PT1.2S'");
+        assert_round_trips("duration'P1DT2H3M4.5S'");
+        assert_round_trips("duration'-P1D'");
+        assert_round_trips("duration'P1Y2M3D'");
+    }
+
+    #[test]
+    fn round_trip_binary() {
+        let data = b"Definitely not a virus";
+        let encoded = engine::GeneralPurpose::new(&alphabet::URL_SAFE, engine::GeneralPurposeConfig::new())
+            .encode(data);
+        assert_round_trips(&format!("binary'{encoded}'"));
+    }
+
+    #[test]
+    fn round_trip_collection() {
+        assert_round_trips("[]");
+        assert_round_trips("[1,2,3]");
+        assert_round_trips("['Food','Beverages']");
+    }
+
+    #[test]
+    fn round_trip_object() {
+        assert_round_trips("{}");
+        assert_round_trips("{\"City\":'Redmond',\"Zip\":98052}");
+    }
+
+    fn assert_expr_round_trips(input: &str) {
+        let (rest, parsed) = expr::parse(input).expect("input should parse");
+        assert!(rest.is_empty(), "Unparsed input: {rest}");
+        let serialized = parsed.to_query_string();
+        let (rest, reparsed) = expr::parse(&serialized)
+            .unwrap_or_else(|e| panic!("{serialized:?} didn't parse: {e}"));
+        assert!(rest.is_empty(), "Unparsed input: {rest}");
+        assert_eq!(reparsed, parsed, "serialized as {serialized:?}");
+    }
+
+    #[test]
+    fn round_trip_filter_expression() {
+        assert_expr_round_trips("Price gt 10 and (Name eq 'foo' or Discontinued eq false)");
+        assert_expr_round_trips("not Discontinued eq true");
+        assert_expr_round_trips("-Price gt -10");
+        assert_expr_round_trips("Price add 1 mul 2 gt 10");
+    }
+}
@@ -1,9 +1,10 @@
 use nom::IResult;
 
 #[cfg(test)]
-pub fn assert_parsed_to<T>(result: IResult<&str, T>, exp: T)
+pub fn assert_parsed_to<T, E>(result: IResult<&str, T, E>, exp: T)
 where
     T: std::fmt::Debug + std::cmp::PartialEq,
+    E: std::fmt::Debug,
 {
     assert!(result.is_ok(), "{:?}", result);
     match result {